@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use automata::prelude::*;
+
+use crate::prefixtree::prefix_tree;
+
+/// Determines which matches a [`PatternMatcher`] reports when scanning an input, mirroring the
+/// match kinds offered by the standard Aho-Corasick implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Report every match, including ones that overlap or are contained in another match.
+    Standard,
+    /// Among matches starting at the same position, only report the one whose pattern appears
+    /// earliest in the list originally passed to [`PatternMatcher::new`] (ties broken by pattern
+    /// priority, not by length); once a match is reported, scanning resumes strictly after it so
+    /// later overlapping matches are suppressed.
+    LeftmostFirst,
+    /// Among matches starting at the same position, only report the longest one; once a match is
+    /// reported, scanning resumes strictly after it.
+    LeftmostLongest,
+}
+
+/// A single occurrence of one of the patterns fed to a [`PatternMatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the matched pattern into the slice that was originally passed to
+    /// [`PatternMatcher::new`].
+    pub pattern: usize,
+    /// Start offset (inclusive) of the match in the scanned input.
+    pub start: usize,
+    /// End offset (exclusive) of the match in the scanned input.
+    pub end: usize,
+}
+
+/// A linear-time multi-pattern matcher, built by compiling Aho-Corasick failure links on top of
+/// the prefix tree produced by [`prefix_tree`].
+///
+/// The trie is constructed once from the given patterns, after which a breadth-first pass
+/// computes for every state its failure link (the longest proper suffix of the state's path that
+/// is also a path in the trie) and its output/dictionary link (the nearest failure ancestor, if
+/// any, at which a pattern ends). Calling [`PatternMatcher::compile`] additionally materializes
+/// the failure function into explicit edges, turning the trie into a complete DFA that can be
+/// driven with the rest of the [`TransitionSystem`] API.
+pub struct PatternMatcher {
+    ts: EdgeListsDeterministic<CharAlphabet, bool, Void>,
+    root: StateIndex,
+    fail: Vec<StateIndex>,
+    /// `out[q]` lists the indices (into `patterns`) of every pattern ending in `q`, including
+    /// those inherited from `q`'s output-link ancestors.
+    out: Vec<Vec<usize>>,
+    patterns: Vec<String>,
+    materialized: bool,
+}
+
+impl PatternMatcher {
+    /// Builds the trie for `patterns` and compiles its failure and output links.
+    pub fn new<I, W>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = W>,
+        W: Into<String>,
+    {
+        let patterns: Vec<String> = patterns.into_iter().map(Into::into).collect();
+        let (ts, root) = prefix_tree(patterns.iter().cloned());
+
+        let size = ts.size();
+        let mut fail = vec![root; size];
+        let mut out: Vec<Vec<usize>> = vec![Vec::new(); size];
+        let mut dict_link: Vec<Option<StateIndex>> = vec![None; size];
+
+        for (idx, word) in patterns.iter().enumerate() {
+            if let Ok(run) = ts.finite_run(word) {
+                out[run.reached().index()].push(idx);
+            }
+        }
+
+        // breadth-first pass: depth-1 states fail to the root, every deeper state's failure link
+        // is derived from its parent's as `fail(child) = goto(fail(parent), sym)`. The output
+        // link of a state is computed in the same pass, since by the time a state is dequeued its
+        // failure link (a strictly shallower state) has already had its own output link resolved.
+        let mut queue: VecDeque<StateIndex> = VecDeque::new();
+        for sym in ts.alphabet().universe() {
+            if let Some((_, target)) = ts.transition(root, &sym) {
+                fail[target.index()] = root;
+                queue.push_back(target);
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let parent_fail = fail[state.index()];
+            dict_link[state.index()] = if !out[parent_fail.index()].is_empty() {
+                Some(parent_fail)
+            } else {
+                dict_link[parent_fail.index()]
+            };
+            if let Some(link) = dict_link[state.index()] {
+                let inherited = out[link.index()].clone();
+                out[state.index()].extend(inherited);
+            }
+
+            for sym in ts.alphabet().universe() {
+                let Some((_, child)) = ts.transition(state, &sym) else {
+                    continue;
+                };
+                fail[child.index()] = goto(&ts, &fail, fail[state.index()], &sym, root);
+                queue.push_back(child);
+            }
+        }
+
+        Self {
+            ts,
+            root,
+            fail,
+            out,
+            patterns,
+            materialized: false,
+        }
+    }
+
+    /// Materializes the failure function into explicit edges, so that every state has an outgoing
+    /// transition for every symbol of the alphabet and the matcher becomes a complete DFA.
+    pub fn compile(&mut self) {
+        if self.materialized {
+            return;
+        }
+        for state in self.ts.state_indices_vec() {
+            for sym in self.ts.alphabet().universe() {
+                if self.ts.transition(state, &sym).is_none() {
+                    let target = goto(&self.ts, &self.fail, self.fail[state.index()], &sym, self.root);
+                    self.ts.add_edge((state, sym, Void, target));
+                }
+            }
+        }
+        self.materialized = true;
+    }
+
+    fn delta(&self, state: StateIndex, sym: &char) -> StateIndex {
+        if let Some((_, target)) = self.ts.transition(state, sym) {
+            return target;
+        }
+        goto(&self.ts, &self.fail, self.fail[state.index()], sym, self.root)
+    }
+
+    /// Returns every match of kind `kind` found while scanning `input`.
+    ///
+    /// Positions are counted in `char`s, not bytes, so they stay consistent with the pattern
+    /// lengths (themselves counted in `chars`) even on multi-byte input.
+    pub fn matches(&self, input: &str, kind: MatchKind) -> Vec<Match> {
+        let mut state = self.root;
+        let mut found = Vec::new();
+        for (i, c) in input.chars().enumerate() {
+            state = self.delta(state, &c);
+            let end = i + 1;
+            for &pattern in &self.out[state.index()] {
+                let len = self.patterns[pattern].chars().count();
+                found.push(Match {
+                    pattern,
+                    start: end - len,
+                    end,
+                });
+            }
+        }
+
+        match kind {
+            MatchKind::Standard => found,
+            MatchKind::LeftmostFirst | MatchKind::LeftmostLongest => {
+                Self::resolve_leftmost(found, kind)
+            }
+        }
+    }
+
+    /// Resolves the (possibly overlapping) `found` matches into the non-overlapping leftmost set
+    /// described by `kind`. Matches are grouped by start offset; starts are then visited in
+    /// increasing order, and for each one not already covered by a previously chosen match, a
+    /// winner is picked among its candidates (the one with the lowest pattern index, i.e.
+    /// appearing earliest in the original pattern list, for [`MatchKind::LeftmostFirst`]; the
+    /// longest for [`MatchKind::LeftmostLongest`]) and scanning conceptually resumes strictly
+    /// after it, suppressing every other candidate it overlaps.
+    fn resolve_leftmost(found: Vec<Match>, kind: MatchKind) -> Vec<Match> {
+        let mut by_start: BTreeMap<usize, Vec<Match>> = BTreeMap::new();
+        for m in found {
+            by_start.entry(m.start).or_default().push(m);
+        }
+
+        let mut resolved = Vec::new();
+        let mut cursor = 0usize;
+        for (start, candidates) in by_start {
+            if start < cursor {
+                continue;
+            }
+            let winner = match kind {
+                MatchKind::LeftmostFirst => candidates.into_iter().min_by_key(|m| m.pattern),
+                MatchKind::LeftmostLongest => candidates.into_iter().max_by_key(|m| m.end),
+                MatchKind::Standard => unreachable!("Standard matches never reach resolve_leftmost"),
+            }
+            .expect("every group has at least one candidate");
+            cursor = winner.end;
+            resolved.push(winner);
+        }
+        resolved
+    }
+}
+
+/// Follows failure links starting from `state` until a state with an outgoing `sym`-edge (or
+/// `root`) is found, mirroring the `goto` function of the classic construction. Note that `fail`
+/// is only fully populated for states already visited by the breadth-first pass in
+/// [`PatternMatcher::new`]; during that pass `goto` is only called with a strictly shallower
+/// `state` than the child currently being processed, and every later call happens once the pass
+/// has completed.
+fn goto(
+    ts: &EdgeListsDeterministic<CharAlphabet, bool, Void>,
+    fail: &[StateIndex],
+    mut state: StateIndex,
+    sym: &char,
+    root: StateIndex,
+) -> StateIndex {
+    while state != root {
+        if let Some((_, target)) = ts.transition(state, sym) {
+            return target;
+        }
+        state = fail[state.index()];
+    }
+    ts.transition(root, sym)
+        .map(|(_, target)| target)
+        .unwrap_or(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overlapping_matches() {
+        let matcher = PatternMatcher::new(["he", "she", "his", "hers"]);
+        let found = matcher.matches("ushers", MatchKind::Standard);
+        let mut pairs: Vec<_> = found.iter().map(|m| (m.pattern, m.start, m.end)).collect();
+        pairs.sort();
+        assert!(pairs.contains(&(1, 1, 4)));
+        assert!(pairs.contains(&(0, 2, 4)));
+        assert!(pairs.contains(&(3, 2, 6)));
+    }
+
+    #[test]
+    fn leftmost_longest_suppresses_shorter_matches() {
+        let matcher = PatternMatcher::new(["a", "ab", "abc"]);
+        let found = matcher.matches("abc", MatchKind::LeftmostLongest);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pattern, 2);
+    }
+
+    #[test]
+    fn leftmost_first_prefers_earlier_pattern_over_shorter_one() {
+        let matcher = PatternMatcher::new(["Samwise", "Sam"]);
+        let found = matcher.matches("Samwise", MatchKind::LeftmostFirst);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pattern, 0);
+    }
+}
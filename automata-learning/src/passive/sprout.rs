@@ -2,7 +2,12 @@ use automata::{math::Set, prelude::*, random, transition_system::path};
 use itertools::Itertools;
 use tracing::{info, warn};
 
-use std::{collections::HashSet, fmt::Debug, path::Iter};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    path::Iter,
+    time::Duration,
+};
 
 use crate::prefixtree::prefix_tree;
 
@@ -24,11 +29,87 @@ impl<A: ConsistencyCheck<WithInitial<DTS>>> Debug for SproutError<A> {
     }
 }
 
+/// Decides, for the escaping transition `source --a-->`, the order in which `sprout` should
+/// attempt merging it into the states already present in `ts`.
+pub trait StateOrderStrategy {
+    /// Returns the candidate target states of `ts` to attempt, in the order `sprout` should try
+    /// them in.
+    fn order(&self, ts: &WithInitial<DTS>, source: StateIndex, a: char) -> Vec<StateIndex>;
+}
+
+/// The original strategy: attempt merges in the length-lexicographic order `state_indices_vec`
+/// yields states in, i.e. by the order in which states were created.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexOrder;
+
+impl StateOrderStrategy for IndexOrder {
+    fn order(&self, ts: &WithInitial<DTS>, _source: StateIndex, _a: char) -> Vec<StateIndex> {
+        ts.state_indices_vec()
+    }
+}
+
+/// Prefers merging into states that are reachable from `source` along a short path, on the
+/// heuristic that nearby states are more likely to produce a small automaton; states unreachable
+/// from `source` are tried last, in index order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferMostRecentlyReachable;
+
+impl StateOrderStrategy for PreferMostRecentlyReachable {
+    fn order(&self, ts: &WithInitial<DTS>, source: StateIndex, _a: char) -> Vec<StateIndex> {
+        let mut distance = HashMap::new();
+        distance.insert(source, 0usize);
+        let mut queue = VecDeque::from([source]);
+        while let Some(q) = queue.pop_front() {
+            let Some(edges) = ts.edges_from(q) else {
+                continue;
+            };
+            let d = distance[&q];
+            for edge in edges {
+                if distance.contains_key(&edge.target()) {
+                    continue;
+                }
+                distance.insert(edge.target(), d + 1);
+                queue.push_back(edge.target());
+            }
+        }
+
+        let mut states = ts.state_indices_vec();
+        states.sort_by_key(|q| distance.get(q).copied().unwrap_or(usize::MAX));
+        states
+    }
+}
+
+/// Configuration for [`sprout`]: the wall-clock timeout after which it gives up with
+/// [`SproutError::Timeout`], an optional override for the escape-prefix length threshold (by
+/// default the longest spoke length plus the square of the longest cycle length in the sample,
+/// plus one), and the
+/// [`StateOrderStrategy`] used to pick which existing state to try merging an escaping transition
+/// into first.
+pub struct SproutConfig<S: StateOrderStrategy = IndexOrder> {
+    /// Wall-clock budget before `sprout` aborts with [`SproutError::Timeout`].
+    pub timeout: Duration,
+    /// Overrides the threshold otherwise derived from the longest spoke/cycle in the sample.
+    pub threshold_override: Option<usize>,
+    /// Strategy used to order candidate merge targets.
+    pub order: S,
+}
+
+impl Default for SproutConfig<IndexOrder> {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60 * 10),
+            threshold_override: None,
+            order: IndexOrder,
+        }
+    }
+}
+
 /// gives a deterministic acc_type omega automaton that is consistent with the given sample
 /// implements the sprout passive learning algorithm for omega automata from <https://arxiv.org/pdf/2108.03735.pdf>
-pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
+pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>, S: StateOrderStrategy>(
     sample: OmegaSample,
     acc_type: A,
+    config: SproutConfig<S>,
 ) -> Result<A::Aut, SproutError<A>> {
     let time_start = std::time::Instant::now();
 
@@ -36,11 +117,13 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
     let mut ts = Automaton::new_with_initial_color(sample.alphabet().clone(), Void);
 
     // compute threshold
-    let (lb, le) = sample
-        .words()
-        .map(|w| (w.spoke().len(), w.cycle().len()))
-        .fold((0, 0), |(a0, a1), (b0, b1)| (a0.max(b0), a1.max(b1)));
-    let thresh = (lb + le.pow(2) + 1) as isize;
+    let thresh = config.threshold_override.unwrap_or_else(|| {
+        let (lb, le) = sample
+            .words()
+            .map(|w| (w.spoke().len(), w.cycle().len()))
+            .fold((0, 0), |(a0, a1), (b0, b1)| (a0.max(b0), a1.max(b1)));
+        lb + le.pow(2) + 1
+    }) as isize;
     info!("starting sprout with threshold {thresh}");
 
     // while there are positive sample words that are escaping
@@ -51,8 +134,7 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
         length_lexicographical_sort(ts.escape_prefixes(mut_sample.positive_words()).collect())
             .first()
     {
-        // WARN TODO should find a way to either pass or globally set timeout
-        if time_start.elapsed() >= std::time::Duration::from_secs(60 * 10) {
+        if time_start.elapsed() >= config.timeout {
             warn!(
                 "task exceeded timeout, aborting with automaton of size {}",
                 ts.size()
@@ -72,7 +154,7 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
         }
         // dbg!(u.len());
         let source = ts.finite_run(&u).unwrap().reached();
-        for q in ts.state_indices_vec() {
+        for q in config.order.order(&ts, source, a) {
             // try adding transition
             ts.add_edge((source, a, Void, q));
             // continue if consistent
@@ -173,7 +255,7 @@ mod tests {
             .default_color(Void)
             .into_dba(0);
 
-        let res = sprout(sample, BuchiCondition).unwrap();
+        let res = sprout(sample, BuchiCondition, SproutConfig::default()).unwrap();
         assert_eq!(res, dba);
     }
 
@@ -207,7 +289,7 @@ mod tests {
             .default_color(Void)
             .into_dba(0);
 
-        let res = sprout(sample, BuchiCondition).err();
+        let res = sprout(sample, BuchiCondition, SproutConfig::default()).err();
         assert!(matches!(res.unwrap(), SproutError::Threshold(_, _, _)))
     }
 
@@ -253,7 +335,7 @@ mod tests {
             .default_color(Void)
             .into_dpa(0);
 
-        let res = sprout(sample, MinEvenParityCondition).unwrap();
+        let res = sprout(sample, MinEvenParityCondition, SproutConfig::default()).unwrap();
         assert_eq!(res, dpa);
     }
 
@@ -283,7 +365,7 @@ mod tests {
             .into_dpa(0);
         dpa.complete_with_colors(Void, 1);
 
-        let res = sprout(sample, MinEvenParityCondition).err();
+        let res = sprout(sample, MinEvenParityCondition, SproutConfig::default()).err();
         assert!(matches!(res.unwrap(), SproutError::Threshold(_, _, _)))
     }
 }
@@ -0,0 +1,191 @@
+//! Builders for automata recognizing digit strings that satisfy a numeric bound, e.g. all base-10
+//! strings denoting a number `<= 42`.
+
+use std::cmp::Ordering;
+
+use crate::prelude::*;
+
+/// The comparison a numeric-constraint automaton accepts against: the digit strings accepted are
+/// exactly those whose value, read most-significant digit first in the given radix, satisfies
+/// `bound` against `n`. Leading zeros are insignificant and inputs may have any number of digits —
+/// `"007"`, `"7"` and `"0007"` are all treated as denoting the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// Accept strings denoting a number `<= n`.
+    Leq,
+    /// Accept strings denoting a number `< n`.
+    Lt,
+    /// Accept strings denoting a number `>= n`.
+    Geq,
+}
+
+/// Which side of `n`'s digit sequence the run is currently tracking, once the input has as many
+/// significant digits as `n` has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Track {
+    /// Every significant digit read so far agrees with the corresponding digit of `n`.
+    Equal,
+    /// The run has already read a significant digit smaller than `n`'s at that position.
+    Less,
+    /// The run has already read a significant digit larger than `n`'s at that position.
+    Greater,
+}
+
+impl Track {
+    fn accepts(self, bound: Bound) -> bool {
+        match (self, bound) {
+            (Track::Less, Bound::Leq | Bound::Lt) => true,
+            (Track::Equal, Bound::Leq | Bound::Geq) => true,
+            (Track::Greater, Bound::Geq) => true,
+            _ => false,
+        }
+    }
+}
+
+fn digits_of(mut n: u64, base: u32) -> Vec<u32> {
+    if n == 0 {
+        return vec![0];
+    }
+    let mut digits = Vec::new();
+    while n > 0 {
+        digits.push((n % base as u64) as u32);
+        n /= base as u64;
+    }
+    digits.reverse();
+    digits
+}
+
+fn digit_symbol(d: u32, base: u32) -> char {
+    char::from_digit(d, base).expect("digit must be representable in the given base")
+}
+
+/// Constructs the automaton described by [`Bound`].
+///
+/// Leading zeros are read on a dedicated state `z` (colored to accept the value `0` itself
+/// against `bound`) that self-loops on `'0'` and only leaves once the first nonzero digit is
+/// seen, so any amount of zero-padding is insignificant. From there the run counts significant
+/// digits, comparing each one against the corresponding digit of `n` and freezing onto an
+/// "already less"/"already greater" track as soon as one diverges. A state reached after `p`
+/// significant digits, for `p` short of `n`'s digit count, always accepts as [`Track::Less`]
+/// regardless of its frozen track: having fewer significant digits than `n` makes the input
+/// strictly smaller no matter which digits came before. Once exactly as many significant digits
+/// as `n` has have been read, the frozen track decides acceptance outright; reading even one more
+/// digit past that point means the input has more significant digits than `n`, which
+/// unconditionally makes it larger, so every such state feeds into a single
+/// permanently-[`Track::Greater`] sink.
+fn numeric_bound(base: u32, n: u64, bound: Bound) -> WithInitial<EdgeListsDeterministic<CharAlphabet, bool, Void>> {
+    let digits = digits_of(n, base);
+    let len = digits.len();
+    let alphabet = CharAlphabet::from_chars((0..base).map(|d| digit_symbol(d, base)));
+
+    let zero_accepts = match bound {
+        Bound::Leq => true,
+        Bound::Lt => n > 0,
+        Bound::Geq => n == 0,
+    };
+    let short_accepts = Track::Less.accepts(bound);
+
+    let mut ts = Automaton::new_with_initial_color(alphabet, zero_accepts);
+    let z = ts.initial();
+
+    // equal[p]/less[p]/greater[p] is the state reached after `p` significant digits have been
+    // read, still tied with `n`'s prefix, already fallen below it, or already risen above it
+    // respectively; index 0 is a placeholder, since position 0 is always `z`.
+    let mut equal = vec![z];
+    let mut less = vec![z];
+    let mut greater = vec![z];
+    for p in 1..=len {
+        let is_final = p == len;
+        equal.push(ts.add_state(if is_final { Track::Equal.accepts(bound) } else { short_accepts }));
+        less.push(ts.add_state(if is_final { Track::Less.accepts(bound) } else { short_accepts }));
+        greater.push(ts.add_state(if is_final { Track::Greater.accepts(bound) } else { short_accepts }));
+    }
+    let overflow = ts.add_state(Track::Greater.accepts(bound));
+
+    let track_of = |d: u32, bound_digit: u32, p: usize| -> StateIndex {
+        match d.cmp(&bound_digit) {
+            Ordering::Less => less[p],
+            Ordering::Equal => equal[p],
+            Ordering::Greater => greater[p],
+        }
+    };
+
+    for d in 0..base {
+        let sym = digit_symbol(d, base);
+        let target = if d == 0 { z } else { track_of(d, digits[0], 1) };
+        ts.add_edge((z, sym, Void, target));
+    }
+
+    for p in 1..len {
+        for d in 0..base {
+            let sym = digit_symbol(d, base);
+            ts.add_edge((equal[p], sym, Void, track_of(d, digits[p], p + 1)));
+            ts.add_edge((less[p], sym, Void, less[p + 1]));
+            ts.add_edge((greater[p], sym, Void, greater[p + 1]));
+        }
+    }
+
+    for d in 0..base {
+        let sym = digit_symbol(d, base);
+        ts.add_edge((equal[len], sym, Void, overflow));
+        ts.add_edge((less[len], sym, Void, overflow));
+        ts.add_edge((greater[len], sym, Void, overflow));
+        ts.add_edge((overflow, sym, Void, overflow));
+    }
+
+    ts
+}
+
+impl DTS {
+    /// Builds the complete DFA over the digit alphabet `0..base` accepting exactly the strings of
+    /// digits (most-significant first, any number of leading zeros, any length) denoting a number
+    /// `<= n` in the given `base`.
+    pub fn numeric_leq(base: u32, n: u64) -> WithInitial<EdgeListsDeterministic<CharAlphabet, bool, Void>> {
+        numeric_bound(base, n, Bound::Leq)
+    }
+
+    /// As [`DTS::numeric_leq`], but for `< n`.
+    pub fn numeric_lt(base: u32, n: u64) -> WithInitial<EdgeListsDeterministic<CharAlphabet, bool, Void>> {
+        numeric_bound(base, n, Bound::Lt)
+    }
+
+    /// As [`DTS::numeric_leq`], but for `>= n`.
+    pub fn numeric_geq(base: u32, n: u64) -> WithInitial<EdgeListsDeterministic<CharAlphabet, bool, Void>> {
+        numeric_bound(base, n, Bound::Geq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_and_rejects_around_the_bound() {
+        let dfa = DTS::numeric_leq(10, 42);
+        let color_of = |w: &str| dfa.state_color(dfa.finite_run(w).unwrap().reached()).unwrap();
+        assert!(color_of("42"));
+        assert!(color_of("07"));
+        assert!(!color_of("43"));
+        assert!(!color_of("99"));
+    }
+
+    #[test]
+    fn fewer_significant_digits_than_the_bound_always_compares_smaller() {
+        let geq = DTS::numeric_geq(10, 42);
+        let color_of = |w: &str| geq.state_color(geq.finite_run(w).unwrap().reached()).unwrap();
+        // "5" has one significant digit against 42's two, so it denotes 5 < 42 and must not
+        // satisfy `>= 42`, even though the digit 5 itself is greater than 42's leading digit 4.
+        assert!(!color_of("5"));
+        assert!(!color_of("9"));
+    }
+
+    #[test]
+    fn more_significant_digits_than_the_bound_always_compares_larger() {
+        let leq = DTS::numeric_leq(10, 42);
+        let color_of = |w: &str| leq.state_color(leq.finite_run(w).unwrap().reached()).unwrap();
+        // "420" has three significant digits against 42's two, so it denotes 420 > 42 and must
+        // not satisfy `<= 42`, even though its leading digits agree with 42's.
+        assert!(!color_of("420"));
+        assert!(color_of("042"));
+    }
+}
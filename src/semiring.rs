@@ -0,0 +1,103 @@
+//! Semirings used to weight the edges of a [`crate::prelude::TransitionSystem`], turning it into
+//! a weighted automaton.
+
+/// A semiring `(K, ⊕, ⊗, 0, 1)`: an additive identity [`Semiring::zero`], a multiplicative
+/// identity [`Semiring::one`], and the combination operators [`Semiring::plus`]/[`Semiring::times`].
+pub trait Semiring: Clone + PartialEq {
+    /// The additive identity `0`.
+    fn zero() -> Self;
+    /// The multiplicative identity `1`.
+    fn one() -> Self;
+    /// Additive combination `⊕`.
+    fn plus(&self, other: &Self) -> Self;
+    /// Multiplicative combination `⊗`.
+    fn times(&self, other: &Self) -> Self;
+
+    /// Whether this semiring is `k`-closed for some finite `k`, i.e. repeated `⊕`-accumulation of
+    /// any element eventually stabilizes. This is what guarantees termination of the generalized
+    /// shortest-distance algorithm; semirings for which it does not hold (e.g. the reals under
+    /// ordinary `+`) can still diverge on cyclic inputs.
+    fn is_k_closed() -> bool {
+        false
+    }
+}
+
+/// The boolean semiring `(false, true, ∨, ∧)`. Every element is idempotent under `⊕`, so it is
+/// `1`-closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Boolean(pub bool);
+
+impl Semiring for Boolean {
+    fn zero() -> Self {
+        Boolean(false)
+    }
+    fn one() -> Self {
+        Boolean(true)
+    }
+    fn plus(&self, other: &Self) -> Self {
+        Boolean(self.0 || other.0)
+    }
+    fn times(&self, other: &Self) -> Self {
+        Boolean(self.0 && other.0)
+    }
+    fn is_k_closed() -> bool {
+        true
+    }
+}
+
+/// The tropical (min-plus) semiring over `f64`, with `0 = +∞` and `1 = 0`. Distances in this
+/// semiring correspond to shortest path weights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tropical(pub f64);
+
+impl Semiring for Tropical {
+    fn zero() -> Self {
+        Tropical(f64::INFINITY)
+    }
+    fn one() -> Self {
+        Tropical(0.0)
+    }
+    fn plus(&self, other: &Self) -> Self {
+        Tropical(self.0.min(other.0))
+    }
+    fn times(&self, other: &Self) -> Self {
+        Tropical(self.0 + other.0)
+    }
+}
+
+/// The probability semiring over `f64` (ordinary `+`/`*`). Distances in this semiring correspond
+/// to reachability probabilities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probability(pub f64);
+
+impl Semiring for Probability {
+    fn zero() -> Self {
+        Probability(0.0)
+    }
+    fn one() -> Self {
+        Probability(1.0)
+    }
+    fn plus(&self, other: &Self) -> Self {
+        Probability(self.0 + other.0)
+    }
+    fn times(&self, other: &Self) -> Self {
+        Probability(self.0 * other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tropical_plus_is_min() {
+        assert_eq!(Tropical(2.0).plus(&Tropical(5.0)), Tropical(2.0));
+        assert_eq!(Tropical(2.0).times(&Tropical(5.0)), Tropical(7.0));
+    }
+
+    #[test]
+    fn boolean_is_or_and() {
+        assert_eq!(Boolean(true).plus(&Boolean(false)), Boolean(true));
+        assert_eq!(Boolean(true).times(&Boolean(false)), Boolean(false));
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+
+use crate::{
+    math::{Map, Set},
+    prelude::*,
+};
+
+/// Decides, given the color of an edge, whether that edge is a silent (epsilon) move that can be
+/// taken for free while computing a [`null_closure`]. Implemented for any `Fn(&C) -> bool`, so a
+/// plain closure can be passed wherever an `EpsilonPredicate` is expected.
+pub trait EpsilonPredicate<C> {
+    /// Returns `true` if an edge colored `color` is an epsilon-transition.
+    fn is_epsilon(&self, color: &C) -> bool;
+}
+
+impl<C, F: Fn(&C) -> bool> EpsilonPredicate<C> for F {
+    fn is_epsilon(&self, color: &C) -> bool {
+        self(color)
+    }
+}
+
+/// A macro-state produced by subset construction: the epsilon-closure of some set of states of
+/// the source transition system.
+pub type MacroState<T> = Set<<T as TransitionSystem>::StateIndex>;
+
+/// Computes the least set of states reachable from `states` by repeatedly following edges that
+/// `is_epsilon` classifies as silent. Uses a worklist so every state is expanded at most once.
+pub fn null_closure<T>(
+    ts: &T,
+    states: impl IntoIterator<Item = T::StateIndex>,
+    is_epsilon: &impl EpsilonPredicate<T::EdgeColor>,
+) -> MacroState<T>
+where
+    T: TransitionSystem,
+{
+    let mut closure: MacroState<T> = states.into_iter().collect();
+    let mut worklist: VecDeque<T::StateIndex> = closure.iter().copied().collect();
+
+    while let Some(q) = worklist.pop_front() {
+        let Some(edges) = ts.edges_from(q) else {
+            continue;
+        };
+        for edge in edges {
+            if is_epsilon.is_epsilon(edge.color()) && closure.insert(edge.target()) {
+                worklist.push_back(edge.target());
+            }
+        }
+    }
+    closure
+}
+
+fn state_colors<T: TransitionSystem>(ts: &T, macro_state: &MacroState<T>) -> Vec<T::StateColor> {
+    macro_state
+        .iter()
+        .map(|q| ts.state_color(*q).expect("macro-state member must exist in ts"))
+        .collect()
+}
+
+/// Determinizes `ts` via subset construction extended with epsilon-closures: a macro-state is the
+/// [`null_closure`] of a set of `T::StateIndex`es, the initial macro-state is the closure of `ts`'s
+/// initial state, and the `a`-successor of a macro-state is the closure of the union of all
+/// `a`-successors of its members. Macro-states are deduplicated by the set of indices they
+/// contain. State and edge colors of `ts` are folded into colors of the result via
+/// `merge_states`/`merge_edges`, so acceptance information survives determinization.
+pub fn determinize<T, Q, C>(
+    ts: &T,
+    is_epsilon: impl EpsilonPredicate<T::EdgeColor>,
+    merge_states: impl Fn(&[T::StateColor]) -> Q,
+    merge_edges: impl Fn(&[T::EdgeColor]) -> C,
+) -> EdgeListsDeterministic<T::Alphabet, Q, C>
+where
+    T: TransitionSystem + Pointed,
+    Q: Color,
+    C: Color,
+{
+    let mut out: EdgeListsDeterministic<T::Alphabet, Q, C> =
+        EdgeLists::for_alphabet(ts.alphabet().clone());
+
+    let initial = null_closure(ts, [ts.initial()], &is_epsilon);
+    let initial_idx = out.add_state(merge_states(&state_colors(ts, &initial)));
+
+    let mut seen: Map<MacroState<T>, StateIndex> = Map::default();
+    seen.insert(initial.clone(), initial_idx);
+    let mut worklist: VecDeque<MacroState<T>> = VecDeque::from([initial]);
+
+    while let Some(macro_state) = worklist.pop_front() {
+        let source = *seen.get(&macro_state).expect("macro-state was enqueued with an entry");
+
+        for sym in ts.alphabet().universe() {
+            let mut successors: MacroState<T> = Set::default();
+            let mut colors = Vec::new();
+
+            for &q in &macro_state {
+                let Some(edges) = ts.edges_from(q) else {
+                    continue;
+                };
+                for edge in edges {
+                    if is_epsilon.is_epsilon(edge.color()) || !edge.expression().matches(&sym) {
+                        continue;
+                    }
+                    successors.insert(edge.target());
+                    colors.push(edge.color().clone());
+                }
+            }
+
+            if successors.is_empty() {
+                continue;
+            }
+
+            let closure = null_closure(ts, successors, &is_epsilon);
+            let target = match seen.get(&closure) {
+                Some(&idx) => idx,
+                None => {
+                    let idx = out.add_state(merge_states(&state_colors(ts, &closure)));
+                    seen.insert(closure.clone(), idx);
+                    worklist.push_back(closure);
+                    idx
+                }
+            };
+            out.add_edge((source, sym, merge_edges(&colors), target));
+        }
+    }
+
+    out
+}
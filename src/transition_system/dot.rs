@@ -0,0 +1,169 @@
+use std::fmt::Write as _;
+use std::io;
+
+use crate::prelude::*;
+
+/// Customizes how a [`GraphWriter`] renders the states of a transition system, e.g. to give
+/// accepting states of a Büchi/parity automaton a different shape or fill color.
+pub trait DotStyle<T: TransitionSystem> {
+    /// The Graphviz node shape to use for a state colored `color`.
+    fn node_shape(&self, _color: &T::StateColor) -> &'static str {
+        "circle"
+    }
+    /// An optional fill color for a state colored `color`; `None` leaves the node unfilled.
+    fn node_fill(&self, _color: &T::StateColor) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Renders every state as an unfilled circle. Used by [`GraphWriter::new`] when no other style is
+/// supplied.
+pub struct DefaultDotStyle;
+impl<T: TransitionSystem> DotStyle<T> for DefaultDotStyle {}
+
+/// Serializes a [`TransitionSystem`] to Graphviz DOT: one node per [`StateIndex`], labeled with
+/// its [`StateColor`] via [`Show`], and one edge per transition, labeled with its [`Expression`]
+/// and [`EdgeColor`]. Node styling is delegated to a [`DotStyle`], so callers can distinguish
+/// accepting states without forking the writer.
+pub struct GraphWriter<'a, T, S = DefaultDotStyle> {
+    ts: &'a T,
+    style: S,
+}
+
+impl<'a, T: TransitionSystem> GraphWriter<'a, T, DefaultDotStyle> {
+    /// Creates a writer for `ts` using the [`DefaultDotStyle`].
+    pub fn new(ts: &'a T) -> Self {
+        Self {
+            ts,
+            style: DefaultDotStyle,
+        }
+    }
+}
+
+impl<'a, T: TransitionSystem, S: DotStyle<T>> GraphWriter<'a, T, S> {
+    /// Creates a writer for `ts` using `style` instead of the default.
+    pub fn with_style(ts: &'a T, style: S) -> Self {
+        Self { ts, style }
+    }
+
+    /// Renders `ts` to a DOT-formatted string.
+    pub fn render(&self) -> String
+    where
+        T::StateColor: Show,
+        T::EdgeColor: Show,
+    {
+        let mut out = String::new();
+        writeln!(out, "digraph A {{").unwrap();
+        self.write_nodes(&mut out);
+        self.write_edges(&mut out);
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    /// Renders `ts` to a DOT-formatted string, additionally drawing an incoming arrow onto the
+    /// initial state.
+    pub fn render_pointed(&self) -> String
+    where
+        T: Pointed,
+        T::StateColor: Show,
+        T::EdgeColor: Show,
+    {
+        let mut out = String::new();
+        writeln!(out, "digraph A {{").unwrap();
+        writeln!(out, "  __start [shape=none, label=\"\"];").unwrap();
+        writeln!(
+            out,
+            "  __start -> {} [style=bold];",
+            self.ts.initial().show()
+        )
+        .unwrap();
+        self.write_nodes(&mut out);
+        self.write_edges(&mut out);
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    fn write_nodes(&self, out: &mut String)
+    where
+        T::StateColor: Show,
+    {
+        for state in self.ts.state_indices() {
+            let color = self
+                .ts
+                .state_color(state)
+                .expect("state index must have an associated color");
+            let shape = self.style.node_shape(&color);
+            write!(
+                out,
+                "  {} [label=\"{} | {}\", shape={shape}",
+                state.show(),
+                state.show(),
+                color.show()
+            )
+            .unwrap();
+            if let Some(fill) = self.style.node_fill(&color) {
+                write!(out, ", style=filled, fillcolor={fill}").unwrap();
+            }
+            writeln!(out, "];").unwrap();
+        }
+    }
+
+    fn write_edges(&self, out: &mut String)
+    where
+        T::EdgeColor: Show,
+    {
+        for state in self.ts.state_indices() {
+            let Some(edges) = self.ts.edges_from(state) else {
+                continue;
+            };
+            for edge in edges {
+                writeln!(
+                    out,
+                    "  {} -> {} [label=\"{} | {}\"];",
+                    state.show(),
+                    edge.target().show(),
+                    edge.expression().show(),
+                    edge.color().show()
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+/// Extension trait adding Graphviz DOT rendering to every [`TransitionSystem`], so learned
+/// automata (e.g. from `sprout`) can be inspected without hand-writing an exporter.
+pub trait ToDot: TransitionSystem {
+    /// Renders `self` to a DOT-formatted string using the [`DefaultDotStyle`].
+    fn to_dot(&self) -> String
+    where
+        Self::StateColor: Show,
+        Self::EdgeColor: Show,
+    {
+        GraphWriter::new(self).render()
+    }
+
+    /// Writes the DOT rendering of `self` to `writer`.
+    fn write_dot<W: io::Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        Self::StateColor: Show,
+        Self::EdgeColor: Show,
+    {
+        writer.write_all(self.to_dot().as_bytes())
+    }
+}
+impl<T: TransitionSystem> ToDot for T {}
+
+/// Extension trait adding Graphviz DOT rendering, with the initial state marked, to every
+/// [`Pointed`] [`TransitionSystem`].
+pub trait ToDotPointed: TransitionSystem + Pointed {
+    /// Renders `self` to a DOT-formatted string, marking the initial state.
+    fn to_dot_pointed(&self) -> String
+    where
+        Self::StateColor: Show,
+        Self::EdgeColor: Show,
+    {
+        GraphWriter::new(self).render_pointed()
+    }
+}
+impl<T: TransitionSystem + Pointed> ToDotPointed for T {}
@@ -0,0 +1,51 @@
+use std::collections::VecDeque;
+
+use crate::{math::Map, prelude::*, semiring::Semiring};
+
+/// Mohri's generalized single-source shortest-distance algorithm (see "Semiring Frameworks and
+/// Algorithms for Shortest-Distance Problems", Mohri 2002), computing for every state reachable
+/// from `source` its distance in the weight semiring `W` carried by `ts`'s edges.
+///
+/// For each state `q` we maintain a tentative distance `d[q]` and a "recently added" value
+/// `r[q]`: both start at [`Semiring::one`] for `source` and [`Semiring::zero`] everywhere else. A
+/// worklist is seeded with `source`; popping a state `q`, we snapshot `r' = r[q]`, reset `r[q]`
+/// to [`Semiring::zero`], and for every edge `q -a-> t` weighted `w` update
+/// `d[t] = d[t] ⊕ (r' ⊗ w)`, enqueuing `t` (and accumulating into `r[t]`) whenever `d[t]` actually
+/// changes. Generic over the [`TransitionSystem`] trait, so both `EdgeLists`- and
+/// `LinkedList`-backed systems work.
+pub fn shortest_distance<T>(ts: &T, source: T::StateIndex) -> Map<T::StateIndex, T::EdgeColor>
+where
+    T: TransitionSystem,
+    T::EdgeColor: Semiring,
+{
+    let mut d: Map<T::StateIndex, T::EdgeColor> = Map::default();
+    let mut r: Map<T::StateIndex, T::EdgeColor> = Map::default();
+    d.insert(source, T::EdgeColor::one());
+    r.insert(source, T::EdgeColor::one());
+
+    let mut queue: VecDeque<T::StateIndex> = VecDeque::from([source]);
+
+    while let Some(q) = queue.pop_front() {
+        let r_q = r.remove(&q).unwrap_or_else(T::EdgeColor::zero);
+        let Some(edges) = ts.edges_from(q) else {
+            continue;
+        };
+        for edge in edges {
+            let t = edge.target();
+            let candidate = r_q.times(edge.color());
+
+            let d_t = d.get(&t).cloned().unwrap_or_else(T::EdgeColor::zero);
+            let updated = d_t.plus(&candidate);
+            if updated != d_t {
+                d.insert(t, updated);
+                let r_t = r.remove(&t).unwrap_or_else(T::EdgeColor::zero).plus(&candidate);
+                r.insert(t, r_t);
+                if !queue.contains(&t) {
+                    queue.push_back(t);
+                }
+            }
+        }
+    }
+
+    d
+}